@@ -0,0 +1,300 @@
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+use std::fmt::Write as _;
+use std::io::{self, Read};
+
+// debug_value recurses once per nesting level, so deeply nested (or
+// malicious) input would otherwise overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+fn escape(data: &[u8]) -> String {
+    data.iter()
+        .flat_map(|&b| std::ascii::escape_default(b))
+        .map(char::from)
+        .collect()
+}
+
+fn split_line(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let pos = data.windows(2).position(|w| w == b"\r\n")?;
+    Some((&data[..pos], &data[pos + 2..]))
+}
+
+struct InvalidLength;
+
+// Same rules as `parse_length` in 36_read_bulk_into: returns None for the `-1`
+// of a null bulk string or array. Only bare digits or the special `-1` are
+// valid; `str::parse` alone would also accept `+11` or `-0`.
+fn parse_length(line: &[u8]) -> Result<Option<usize>, InvalidLength> {
+    if line == b"-1" {
+        return Ok(None);
+    }
+
+    if line.is_empty() || !line.iter().all(u8::is_ascii_digit) {
+        return Err(InvalidLength);
+    }
+
+    std::str::from_utf8(line)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .map(Some)
+        .ok_or(InvalidLength)
+}
+
+fn annotate(out: &mut String, depth: usize, text: &str, note: &str) {
+    writeln!(out, "{}{}  -> {}", "  ".repeat(depth), text, note).unwrap();
+}
+
+// Annotates a single value and returns the remaining input, or None when the
+// value is invalid or truncated and annotation cannot continue.
+fn debug_value<'a>(out: &mut String, data: &'a [u8], depth: usize) -> Option<&'a [u8]> {
+    let (line, rest) = match split_line(data) {
+        Some(parts) => parts,
+        None => {
+            annotate(
+                out,
+                depth,
+                &escape(data),
+                "incomplete line, missing terminator",
+            );
+            return None;
+        }
+    };
+
+    let header = escape(line);
+
+    let (&type_byte, body) = match line.split_first() {
+        Some(parts) => parts,
+        None => {
+            annotate(out, depth, "", "empty line, missing type byte");
+            return None;
+        }
+    };
+
+    match type_byte {
+        b'+' => {
+            annotate(out, depth, &header, "simple string");
+            Some(rest)
+        }
+        b'-' => {
+            annotate(out, depth, &header, "error");
+            Some(rest)
+        }
+        b':' => {
+            annotate(out, depth, &header, "integer");
+            Some(rest)
+        }
+        b'$' => match parse_length(body) {
+            Ok(None) => {
+                annotate(out, depth, &header, "null bulk string");
+                Some(rest)
+            }
+            Ok(Some(length)) => {
+                annotate(
+                    out,
+                    depth,
+                    &header,
+                    &format!("bulk string, length {}", length),
+                );
+
+                if rest.len() < length {
+                    // Counted from the type byte, so a buffering reader knows how
+                    // much of the frame it still has to wait for.
+                    let frame_len = line.len() + 2 + length + 2;
+                    let note = format!(
+                        "truncated payload, {} of {} bytes, frame is {} bytes",
+                        rest.len(),
+                        length,
+                        frame_len
+                    );
+                    annotate(out, depth, &escape(rest), &note);
+                    return None;
+                }
+
+                let (payload, rest) = rest.split_at(length);
+                annotate(out, depth, &escape(payload), "payload");
+
+                if !rest.starts_with(b"\r\n") {
+                    let found = &rest[..rest.len().min(2)];
+                    annotate(out, depth, &escape(found), "missing terminator");
+                    return None;
+                }
+
+                annotate(out, depth, "\\r\\n", "terminator");
+                Some(&rest[2..])
+            }
+            Err(InvalidLength) => {
+                annotate(out, depth, &header, "bulk string, invalid length");
+                None
+            }
+        },
+        b'*' => match parse_length(body) {
+            Ok(None) => {
+                annotate(out, depth, &header, "null array");
+                Some(rest)
+            }
+            Ok(Some(_)) if depth >= MAX_DEPTH => {
+                annotate(out, depth, &header, "array, nesting too deep");
+                None
+            }
+            Ok(Some(count)) => {
+                annotate(out, depth, &header, &format!("array, length {}", count));
+
+                let mut rest = rest;
+                for _ in 0..count {
+                    rest = debug_value(out, rest, depth + 1)?;
+                }
+                Some(rest)
+            }
+            Err(InvalidLength) => {
+                annotate(out, depth, &header, "array, invalid length");
+                None
+            }
+        },
+        _ => {
+            let note = format!("unknown type byte '{}'", escape(&[type_byte]));
+            annotate(out, depth, &header, &note);
+            None
+        }
+    }
+}
+
+fn debug_frame(data: &[u8]) -> String {
+    let mut out = String::new();
+    let mut rest = data;
+
+    while !rest.is_empty() {
+        match debug_value(&mut out, rest, 0) {
+            Some(remaining) => rest = remaining,
+            None => break,
+        }
+    }
+
+    out
+}
+
+fn main() -> io::Result<()> {
+    let mut data = Vec::new();
+    io::stdin().read_to_end(&mut data)?;
+
+    print!("{}", debug_frame(&data));
+
+    Ok(())
+}
+
+#[test]
+fn test_split_line_empty_first_line() {
+    assert_eq!(split_line(b"\r\nfoo"), Some((&b""[..], &b"foo"[..])));
+    assert_eq!(
+        split_line(b"\r\n\r\nfoo"),
+        Some((&b""[..], &b"\r\nfoo"[..]))
+    );
+}
+
+#[test]
+fn test_debug_frame_bulk_string() {
+    assert_eq!(
+        debug_frame(b"$11\r\nhello world\r\n"),
+        "$11  -> bulk string, length 11\n\
+         hello world  -> payload\n\
+         \\r\\n  -> terminator\n"
+    );
+}
+
+#[test]
+fn test_debug_frame_nested_array() {
+    assert_eq!(
+        debug_frame(b"*2\r\n:1\r\n*1\r\n+OK\r\n"),
+        "*2  -> array, length 2\n\
+         \x20 :1  -> integer\n\
+         \x20 *1  -> array, length 1\n\
+         \x20   +OK  -> simple string\n"
+    );
+}
+
+#[test]
+fn test_debug_frame_multiple_frames() {
+    assert_eq!(
+        debug_frame(b"+OK\r\n$-1\r\nZZZ\r\n"),
+        "+OK  -> simple string\n\
+         $-1  -> null bulk string\n\
+         ZZZ  -> unknown type byte 'Z'\n"
+    );
+}
+
+#[test]
+fn test_debug_frame_truncated_payload() {
+    assert_eq!(
+        debug_frame(b"$11\r\nhel"),
+        "$11  -> bulk string, length 11\n\
+         hel  -> truncated payload, 3 of 11 bytes, frame is 18 bytes\n"
+    );
+}
+
+// A length line without its `\r\n` may simply not have arrived yet, so `$11`
+// is incomplete just like a bare `$`. Only a terminated line without digits
+// has an invalid length.
+#[test]
+fn test_debug_frame_unterminated_length_line() {
+    assert_eq!(
+        debug_frame(b"$11"),
+        "$11  -> incomplete line, missing terminator\n"
+    );
+    assert_eq!(
+        debug_frame(b"$"),
+        "$  -> incomplete line, missing terminator\n"
+    );
+    assert_eq!(debug_frame(b"$\r\n"), "$  -> bulk string, invalid length\n");
+}
+
+#[test]
+fn test_debug_frame_null_vs_empty_array() {
+    assert_eq!(
+        debug_frame(b"*-1\r\n*0\r\n"),
+        "*-1  -> null array\n\
+         *0  -> array, length 0\n"
+    );
+}
+
+#[test]
+fn test_debug_frame_null_and_invalid_lengths() {
+    assert_eq!(
+        debug_frame(b"$-1\r\n*-1\r\n$-2\r\n"),
+        "$-1  -> null bulk string\n\
+         *-1  -> null array\n\
+         $-2  -> bulk string, invalid length\n"
+    );
+}
+
+#[test]
+fn test_debug_frame_signed_length() {
+    assert_eq!(
+        debug_frame(b"$+11\r\nhello world\r\n"),
+        "$+11  -> bulk string, invalid length\n"
+    );
+    assert_eq!(
+        debug_frame(b"$-0\r\n\r\n"),
+        "$-0  -> bulk string, invalid length\n"
+    );
+    assert_eq!(
+        debug_frame(b"*+1\r\n:1\r\n"),
+        "*+1  -> array, invalid length\n"
+    );
+}
+
+#[test]
+fn test_debug_frame_deep_nesting() {
+    let data = b"*1\r\n".repeat(200_000);
+    let out = debug_frame(&data);
+    let lines: Vec<&str> = out.lines().collect();
+
+    assert_eq!(lines.len(), MAX_DEPTH + 1);
+    assert!(lines[MAX_DEPTH - 1].ends_with("*1  -> array, length 1"));
+    assert!(lines[MAX_DEPTH].ends_with("*1  -> array, nesting too deep"));
+}
+
+#[quickcheck]
+fn debug_frame_never_panics(data: Vec<u8>) -> bool {
+    debug_frame(&data);
+    true
+}
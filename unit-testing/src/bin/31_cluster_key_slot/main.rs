@@ -0,0 +1,91 @@
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+#[cfg(test)]
+use quickcheck::TestResult;
+use std::env;
+
+const SLOT_COUNT: u16 = 16384;
+
+// CRC16-CCITT (XMODEM variant) as used by Redis Cluster: polynomial 0x1021,
+// initial value 0, no reflection.
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+
+    for &byte in data {
+        crc ^= u16::from(byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+
+    crc
+}
+
+// Only the part between the first `{` and the following `}` is hashed, as
+// long as it is not empty. Otherwise the whole key is hashed.
+fn hash_tag(key: &[u8]) -> &[u8] {
+    if let Some(start) = key.iter().position(|&b| b == b'{') {
+        if let Some(len) = key[start + 1..].iter().position(|&b| b == b'}') {
+            if len > 0 {
+                return &key[start + 1..start + 1 + len];
+            }
+        }
+    }
+
+    key
+}
+
+fn key_slot(key: &[u8]) -> u16 {
+    crc16(hash_tag(key)) % SLOT_COUNT
+}
+
+fn main() {
+    for key in env::args().skip(1) {
+        println!("{} -> {}", key, key_slot(key.as_bytes()));
+    }
+}
+
+#[test]
+fn test_crc16_check_value() {
+    assert_eq!(crc16(b"123456789"), 0x31c3);
+}
+
+#[test]
+fn test_key_slot_known_values() {
+    assert_eq!(key_slot(b"foo"), 12182);
+    assert_eq!(key_slot(b"somekey"), 11058);
+    assert_eq!(key_slot(b"user1000"), 3443);
+    assert_eq!(key_slot(b"{user1000}.following"), key_slot(b"user1000"));
+    assert_eq!(
+        key_slot(b"{user1000}.following"),
+        key_slot(b"{user1000}.followers")
+    );
+}
+
+#[test]
+fn test_key_slot_ignores_empty_or_unclosed_tags() {
+    assert_eq!(key_slot(b"foo{}{bar}"), crc16(b"foo{}{bar}") % SLOT_COUNT);
+    assert_eq!(key_slot(b"foo{bar"), crc16(b"foo{bar") % SLOT_COUNT);
+    assert_eq!(key_slot(b"foo{{bar}}zap"), key_slot(b"{bar"));
+}
+
+#[quickcheck]
+fn key_slot_is_in_range(key: Vec<u8>) -> bool {
+    key_slot(&key) < SLOT_COUNT
+}
+
+#[quickcheck]
+fn key_slot_only_depends_on_hash_tag(tag: String, prefix: String, suffix: String) -> TestResult {
+    if tag.is_empty() || tag.contains('{') || tag.contains('}') || prefix.contains('{') {
+        return TestResult::discard();
+    }
+
+    let key = format!("{}{{{}}}{}", prefix, tag, suffix);
+
+    TestResult::from_bool(key_slot(key.as_bytes()) == key_slot(tag.as_bytes()))
+}
@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+
+type Info = BTreeMap<String, BTreeMap<String, String>>;
+
+// Fields that appear before the first `# Section` header are collected under
+// the empty section name. Lines without a `key:value` separator are skipped.
+fn parse_info(text: &str) -> Info {
+    let mut info = Info::new();
+    let mut section = String::new();
+
+    for line in text.lines() {
+        let line = line.trim_end_matches('\r');
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('#') {
+            section = name.trim().to_string();
+            info.entry(section.clone()).or_default();
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once(':') {
+            info.entry(section.clone())
+                .or_default()
+                .insert(key.to_string(), value.to_string());
+        }
+    }
+
+    info
+}
+
+fn main() -> io::Result<()> {
+    let mut text = String::new();
+    io::stdin().read_to_string(&mut text)?;
+
+    for (section, fields) in parse_info(&text) {
+        println!("[{}]", section);
+        for (key, value) in fields {
+            println!("  {} = {}", key, value);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_info_sections() {
+    let text = "# Server\r\n\
+                redis_version:6.0.9\r\n\
+                tcp_port:6379\r\n\
+                \r\n\
+                # Keyspace\r\n\
+                db0:keys=1,expires=0,avg_ttl=0\r\n";
+
+    let info = parse_info(text);
+
+    assert_eq!(info.len(), 2);
+    assert_eq!(info["Server"]["redis_version"], "6.0.9");
+    assert_eq!(info["Server"]["tcp_port"], "6379");
+    assert_eq!(info["Keyspace"]["db0"], "keys=1,expires=0,avg_ttl=0");
+}
+
+#[test]
+fn test_parse_info_value_containing_colon() {
+    let info = parse_info("# Server\nexecutable:/usr/bin/redis-server\nconfig_file:\n");
+
+    assert_eq!(info["Server"]["executable"], "/usr/bin/redis-server");
+    assert_eq!(info["Server"]["config_file"], "");
+}
+
+#[test]
+fn test_parse_info_fields_before_first_section() {
+    let info = parse_info("role:master\n# Replication\nconnected_slaves:0\n");
+
+    assert_eq!(info[""]["role"], "master");
+    assert_eq!(info["Replication"]["connected_slaves"], "0");
+}
@@ -0,0 +1,58 @@
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+use std::io::{self, Read};
+
+// RESP2 type bytes followed by the RESP3 additions.
+const TYPE_PREFIXES: &[u8] = b"+-:$*_,#!=(%~>|";
+
+// This is only a heuristic: it returns the offset of the next byte that could
+// start a RESP frame, but payload bytes can look like type prefixes too, so
+// the caller still has to try parsing from there. The search starts at offset
+// 0, so a caller skipping past a frame that failed to parse should pass the
+// data after the offending byte.
+fn resync(data: &[u8]) -> Option<usize> {
+    data.iter().position(|b| TYPE_PREFIXES.contains(b))
+}
+
+fn main() -> io::Result<()> {
+    let mut data = Vec::new();
+    io::stdin().read_to_end(&mut data)?;
+
+    match resync(&data) {
+        Some(offset) => println!("next frame candidate at offset {}", offset),
+        None => println!("no frame candidate found"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_resync_skips_garbage() {
+    let data = b"garbage+OK\r\n";
+
+    assert_eq!(resync(data), Some(7));
+    assert_eq!(&data[7..], b"+OK\r\n");
+}
+
+#[test]
+fn test_resync_frame_at_start() {
+    assert_eq!(resync(b"$3\r\nfoo\r\n"), Some(0));
+}
+
+#[test]
+fn test_resync_no_candidate() {
+    assert_eq!(resync(b"garbage"), None);
+    assert_eq!(resync(b""), None);
+}
+
+#[quickcheck]
+fn resync_points_at_first_type_prefix(data: Vec<u8>) -> bool {
+    match resync(&data) {
+        Some(offset) => {
+            TYPE_PREFIXES.contains(&data[offset])
+                && data[..offset].iter().all(|b| !TYPE_PREFIXES.contains(b))
+        }
+        None => data.iter().all(|b| !TYPE_PREFIXES.contains(b)),
+    }
+}
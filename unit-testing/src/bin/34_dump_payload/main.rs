@@ -0,0 +1,107 @@
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+use std::io::{self, Read};
+
+// 2-byte RDB version followed by an 8-byte CRC64, both little endian.
+const FOOTER_LEN: usize = 10;
+
+#[derive(Debug, PartialEq)]
+enum RespError {
+    PayloadTooShort,
+    ChecksumMismatch { expected: u64, found: u64 },
+}
+
+// CRC-64/Jones as used by Redis: polynomial 0xad93d23594c935a9
+// (0x95ac9329ac4bc9b5 bit-reflected, which is the form used below), reflected
+// in/out, initial value 0, no final xor.
+fn crc64(data: &[u8]) -> u64 {
+    let mut crc: u64 = 0;
+
+    for &byte in data {
+        crc ^= u64::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0x95ac_9329_ac4b_c9b5
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    crc
+}
+
+// The checksum covers everything before it, including the RDB version.
+fn validate_dump_payload(bytes: &[u8]) -> Result<(), RespError> {
+    if bytes.len() < FOOTER_LEN {
+        return Err(RespError::PayloadTooShort);
+    }
+
+    let (data, checksum) = bytes.split_at(bytes.len() - 8);
+
+    let mut found = [0; 8];
+    found.copy_from_slice(checksum);
+    let found = u64::from_le_bytes(found);
+
+    let expected = crc64(data);
+
+    if expected != found {
+        return Err(RespError::ChecksumMismatch { expected, found });
+    }
+
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    let mut data = Vec::new();
+    io::stdin().read_to_end(&mut data)?;
+
+    match validate_dump_payload(&data) {
+        Ok(()) => println!("valid DUMP payload"),
+        Err(e) => println!("invalid DUMP payload: {:?}", e),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_crc64_check_value() {
+    assert_eq!(crc64(b"123456789"), 0xe9c6_d914_c4b8_d9ca);
+}
+
+#[test]
+fn test_validate_known_dump_payload() {
+    // `SET mykey 10` followed by `DUMP mykey`, from the Redis documentation.
+    let payload = b"\x00\xc0\n\t\x00\xbem\x06\x89Z(\x00\n";
+
+    assert_eq!(validate_dump_payload(payload), Ok(()));
+}
+
+#[test]
+fn test_validate_corrupt_dump_payload() {
+    let payload = b"\x00\xc0\x0b\t\x00\xbem\x06\x89Z(\x00\n";
+
+    match validate_dump_payload(payload) {
+        Err(RespError::ChecksumMismatch { .. }) => {}
+        other => panic!("Expected checksum mismatch, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_validate_short_dump_payload() {
+    assert_eq!(
+        validate_dump_payload(b"\t\x00\xbem\x06"),
+        Err(RespError::PayloadTooShort)
+    );
+}
+
+#[quickcheck]
+fn payload_with_appended_crc_is_valid(data: Vec<u8>, version: u16) -> bool {
+    let mut payload = data;
+    payload.extend_from_slice(&version.to_le_bytes());
+    let crc = crc64(&payload);
+    payload.extend_from_slice(&crc.to_le_bytes());
+
+    validate_dump_payload(&payload).is_ok()
+}
@@ -0,0 +1,63 @@
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+
+type Client = BTreeMap<String, String>;
+
+// One client per line, as space-separated `key=value` fields. Values may be
+// empty (e.g. `name=`); tokens without a `=` are skipped.
+fn parse_client_list(text: &str) -> Vec<Client> {
+    text.lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.split(' ')
+                .filter_map(|field| field.split_once('='))
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect()
+        })
+        .collect()
+}
+
+fn main() -> io::Result<()> {
+    let mut text = String::new();
+    io::stdin().read_to_string(&mut text)?;
+
+    for client in parse_client_list(&text) {
+        let fields: Vec<String> = client
+            .iter()
+            .map(|(key, value)| format!("{}={:?}", key, value))
+            .collect();
+        println!("{}", fields.join(", "));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_client_list_two_clients() {
+    let text = "id=3 addr=127.0.0.1:50188 fd=8 name= db=0 cmd=client\n\
+                id=4 addr=127.0.0.1:50190 fd=9 name=worker db=2 cmd=get\n";
+
+    let clients = parse_client_list(text);
+
+    assert_eq!(clients.len(), 2);
+    assert_eq!(clients[0]["id"], "3");
+    assert_eq!(clients[0]["addr"], "127.0.0.1:50188");
+    assert_eq!(clients[0]["name"], "");
+    assert_eq!(clients[1]["name"], "worker");
+    assert_eq!(clients[1]["db"], "2");
+}
+
+#[test]
+fn test_parse_client_info_single_line() {
+    let clients = parse_client_list("id=7 laddr=127.0.0.1:6379 flags=N\r\n");
+
+    assert_eq!(clients.len(), 1);
+    assert_eq!(clients[0]["laddr"], "127.0.0.1:6379");
+    assert_eq!(clients[0]["flags"], "N");
+}
+
+#[test]
+fn test_parse_client_list_empty() {
+    assert!(parse_client_list("").is_empty());
+}
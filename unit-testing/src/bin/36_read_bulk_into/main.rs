@@ -0,0 +1,499 @@
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+use std::fmt;
+use std::io::{self, Read};
+
+// Long enough for the `$`, any usize length and the trailing `\r\n`.
+const MAX_HEADER_LEN: usize = 24;
+
+// Upper bound on the bytes of an incomplete frame kept in `Truncated`, so a
+// large bulk string cut off mid-payload does not get copied into the error.
+const MAX_PARTIAL_LEN: usize = 64;
+
+// Strict mode rejects lengths with leading zeros (`$007`), which a
+// spec-compliant server never sends. Lenient mode accepts them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum LengthMode {
+    Lenient,
+    Strict,
+}
+
+#[derive(Debug)]
+enum RespError {
+    Io(io::Error),
+    String(String),
+    InvalidData,
+    InvalidLength,
+    NonCanonicalLength,
+    Utf8 { valid_up_to: usize },
+    LengthMismatch { expected: usize, found: usize },
+    MissingTerminator,
+    Truncated { partial: Vec<u8> },
+}
+
+impl fmt::Display for RespError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RespError::Io(e) => write!(f, "I/O error: {}", e),
+            RespError::String(message) => write!(f, "{}", message),
+            RespError::InvalidData => write!(f, "expected a bulk string"),
+            RespError::InvalidLength => write!(f, "invalid bulk string length"),
+            RespError::NonCanonicalLength => write!(f, "bulk string length has leading zeros"),
+            RespError::Utf8 { valid_up_to } => write!(f, "invalid utf-8 at byte {}", valid_up_to),
+            RespError::LengthMismatch { expected, found } => write!(
+                f,
+                "expected a bulk string of length {}, found {}",
+                expected, found
+            ),
+            RespError::MissingTerminator => write!(f, "missing bulk string terminator"),
+            RespError::Truncated { partial } => write!(
+                f,
+                "connection closed mid-frame after {:?}",
+                String::from_utf8_lossy(partial)
+            ),
+        }
+    }
+}
+
+// io::Error is not PartialEq, so two I/O errors compare equal when their
+// messages do. All other variants are compared structurally.
+impl PartialEq for RespError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (RespError::Io(a), RespError::Io(b)) => a.to_string() == b.to_string(),
+            (RespError::String(a), RespError::String(b)) => a == b,
+            (RespError::InvalidData, RespError::InvalidData)
+            | (RespError::InvalidLength, RespError::InvalidLength)
+            | (RespError::NonCanonicalLength, RespError::NonCanonicalLength)
+            | (RespError::MissingTerminator, RespError::MissingTerminator) => true,
+            (RespError::Utf8 { valid_up_to: a }, RespError::Utf8 { valid_up_to: b }) => a == b,
+            (
+                RespError::LengthMismatch { expected, found },
+                RespError::LengthMismatch {
+                    expected: other_expected,
+                    found: other_found,
+                },
+            ) => expected == other_expected && found == other_found,
+            (RespError::Truncated { partial: a }, RespError::Truncated { partial: b }) => a == b,
+            _ => false,
+        }
+    }
+}
+
+// io::Error is not Clone either, so cloning an I/O error is lossy: the clone
+// is a String variant holding only its message, e.g. for retry logic that
+// keeps the last error around.
+impl Clone for RespError {
+    fn clone(&self) -> Self {
+        match self {
+            RespError::Io(e) => RespError::String(e.to_string()),
+            RespError::String(message) => RespError::String(message.clone()),
+            RespError::InvalidData => RespError::InvalidData,
+            RespError::InvalidLength => RespError::InvalidLength,
+            RespError::NonCanonicalLength => RespError::NonCanonicalLength,
+            RespError::Utf8 { valid_up_to } => RespError::Utf8 {
+                valid_up_to: *valid_up_to,
+            },
+            RespError::LengthMismatch { expected, found } => RespError::LengthMismatch {
+                expected: *expected,
+                found: *found,
+            },
+            RespError::MissingTerminator => RespError::MissingTerminator,
+            RespError::Truncated { partial } => RespError::Truncated {
+                partial: partial.clone(),
+            },
+        }
+    }
+}
+
+impl From<io::Error> for RespError {
+    fn from(e: io::Error) -> Self {
+        RespError::Io(e)
+    }
+}
+
+// Like `read_exact`, but returns how many bytes were read before EOF instead
+// of discarding them, so a truncated frame can be reported.
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(filled)
+}
+
+fn truncated(parts: &[&[u8]]) -> RespError {
+    let partial = parts
+        .iter()
+        .flat_map(|part| part.iter())
+        .copied()
+        .take(MAX_PARTIAL_LEN)
+        .collect();
+
+    RespError::Truncated { partial }
+}
+
+// Parses a length line without its `\r\n`, returning None for the `-1` of a
+// null bulk string. Only bare digits or the special `-1` are valid, so the
+// signs that `str::parse` would otherwise accept (`+5`, `-0`) are rejected.
+fn parse_length(line: &[u8]) -> Result<Option<usize>, RespError> {
+    let line = std::str::from_utf8(line).map_err(|e| RespError::Utf8 {
+        valid_up_to: e.valid_up_to(),
+    })?;
+
+    if line == "-1" {
+        return Ok(None);
+    }
+
+    if line.is_empty() || !line.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(RespError::InvalidLength);
+    }
+
+    line.parse().map(Some).map_err(|_| RespError::InvalidLength)
+}
+
+// Reads the `$<length>\r\n` header into `header`, a stack buffer, so the
+// whole read happens without a heap allocation. Returns the header length.
+// EOF before the first byte is a plain I/O error; EOF after it is a
+// truncated frame.
+fn read_bulk_header<R: Read>(
+    reader: &mut R,
+    header: &mut [u8; MAX_HEADER_LEN],
+) -> Result<usize, RespError> {
+    let mut len = 0;
+
+    loop {
+        if len == header.len() {
+            return Err(RespError::InvalidLength);
+        }
+
+        if read_full(reader, &mut header[len..=len])? == 0 {
+            if len == 0 {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+            }
+            return Err(truncated(&[&header[..len]]));
+        }
+        len += 1;
+
+        if len == 1 && header[0] != b'$' {
+            return Err(RespError::InvalidData);
+        }
+
+        if header[..len].ends_with(b"\r\n") {
+            return Ok(len);
+        }
+    }
+}
+
+// Returns None for a null bulk string, leaving `buf` untouched.
+fn read_bulk_into<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<Option<usize>, RespError> {
+    read_bulk_into_with(reader, buf, LengthMode::Lenient)
+}
+
+fn read_bulk_into_with<R: Read>(
+    reader: &mut R,
+    buf: &mut [u8],
+    mode: LengthMode,
+) -> Result<Option<usize>, RespError> {
+    let mut header = [0; MAX_HEADER_LEN];
+    let header_len = read_bulk_header(reader, &mut header)?;
+    let header = &header[..header_len];
+
+    let line = &header[1..header_len - 2];
+    let length = match parse_length(line)? {
+        Some(length) => length,
+        None => return Ok(None),
+    };
+
+    if mode == LengthMode::Strict && line.len() > 1 && line[0] == b'0' {
+        return Err(RespError::NonCanonicalLength);
+    }
+
+    if length != buf.len() {
+        return Err(RespError::LengthMismatch {
+            expected: buf.len(),
+            found: length,
+        });
+    }
+
+    let read = read_full(reader, buf)?;
+    if read < length {
+        return Err(truncated(&[header, &buf[..read]]));
+    }
+
+    let mut terminator = [0; 2];
+    let read = read_full(reader, &mut terminator)?;
+    if read < terminator.len() {
+        return Err(truncated(&[header, buf, &terminator[..read]]));
+    }
+
+    if &terminator != b"\r\n" {
+        return Err(RespError::MissingTerminator);
+    }
+
+    Ok(Some(length))
+}
+
+fn main() {
+    let data = b"$05\r\nhello\r\n";
+    let mut token = [0; 5];
+
+    match read_bulk_into(&mut &data[..], &mut token) {
+        Ok(None) => println!("lenient: null bulk string"),
+        Ok(Some(n)) => println!(
+            "lenient: read {} bytes: {:?}",
+            n,
+            String::from_utf8_lossy(&token)
+        ),
+        Err(e) => println!("lenient: error: {}", e),
+    }
+
+    match read_bulk_into_with(&mut &data[..], &mut token, LengthMode::Strict) {
+        Ok(None) => println!("strict: null bulk string"),
+        Ok(Some(n)) => println!(
+            "strict: read {} bytes: {:?}",
+            n,
+            String::from_utf8_lossy(&token)
+        ),
+        Err(e) => println!("strict: error: {}", e),
+    }
+}
+
+#[test]
+fn test_read_bulk_into_fixed_buffer() {
+    let mut reader = &b"$5\r\nhello\r\n+OK\r\n"[..];
+    let mut buf = [0; 5];
+
+    assert_eq!(read_bulk_into(&mut reader, &mut buf).unwrap(), Some(5));
+    assert_eq!(&buf, b"hello");
+    assert_eq!(reader, b"+OK\r\n");
+}
+
+#[test]
+fn test_read_bulk_into_length_mismatch() {
+    let mut buf = [0; 16];
+
+    assert_eq!(
+        read_bulk_into(&mut &b"$5\r\nhello\r\n"[..], &mut buf),
+        Err(RespError::LengthMismatch {
+            expected: 16,
+            found: 5,
+        })
+    );
+}
+
+#[test]
+fn test_read_bulk_into_null() {
+    let mut buf = [0; 5];
+
+    assert_eq!(
+        read_bulk_into(&mut &b"$-1\r\n"[..], &mut buf).unwrap(),
+        None
+    );
+    assert_eq!(&buf, &[0; 5]);
+}
+
+#[test]
+fn test_parse_length() {
+    assert_eq!(parse_length(b"11").unwrap(), Some(11));
+    assert_eq!(parse_length(b"0").unwrap(), Some(0));
+    assert_eq!(parse_length(b"-1").unwrap(), None);
+
+    for line in &[&b""[..], b"-2", b"+1", b"1x"] {
+        assert_eq!(
+            parse_length(line),
+            Err(RespError::InvalidLength),
+            "{:?}",
+            line
+        );
+    }
+}
+
+#[test]
+fn test_read_bulk_into_rejects_signed_length() {
+    let mut buf = [0; 5];
+
+    assert_eq!(
+        read_bulk_into(&mut &b"$+5\r\nhello\r\n"[..], &mut buf),
+        Err(RespError::InvalidLength)
+    );
+
+    assert_eq!(
+        read_bulk_into(&mut &b"$-0\r\n\r\n"[..], &mut []),
+        Err(RespError::InvalidLength)
+    );
+}
+
+#[test]
+fn test_read_bulk_into_non_utf8_length() {
+    let mut buf = [0; 1];
+
+    assert_eq!(
+        read_bulk_into(&mut &b"$1\xff\r\nx\r\n"[..], &mut buf),
+        Err(RespError::Utf8 { valid_up_to: 1 })
+    );
+}
+
+#[test]
+fn test_read_bulk_into_leading_zeros() {
+    let data = b"$007\r\npayload\r\n";
+    let mut buf = [0; 7];
+
+    assert_eq!(
+        read_bulk_into_with(&mut &data[..], &mut buf, LengthMode::Strict),
+        Err(RespError::NonCanonicalLength)
+    );
+
+    assert_eq!(read_bulk_into(&mut &data[..], &mut buf).unwrap(), Some(7));
+    assert_eq!(&buf, b"payload");
+}
+
+#[test]
+fn test_read_bulk_into_strict_accepts_zero_length() {
+    let result = read_bulk_into_with(&mut &b"$0\r\n\r\n"[..], &mut [], LengthMode::Strict);
+
+    assert_eq!(result.unwrap(), Some(0));
+}
+
+#[test]
+fn test_read_bulk_into_missing_terminator() {
+    let mut buf = [0; 5];
+
+    assert_eq!(
+        read_bulk_into(&mut &b"$5\r\nhelloXX"[..], &mut buf),
+        Err(RespError::MissingTerminator)
+    );
+}
+
+#[test]
+fn test_resp_error_eq() {
+    let reset = || RespError::from(io::Error::other("reset"));
+    let refused = RespError::from(io::Error::other("refused"));
+
+    assert_eq!(RespError::InvalidData, RespError::InvalidData);
+    assert_ne!(RespError::InvalidData, RespError::InvalidLength);
+    assert_eq!(reset(), reset());
+    assert_ne!(reset(), refused);
+}
+
+#[test]
+fn test_resp_error_clone() {
+    assert_eq!(RespError::InvalidData.clone(), RespError::InvalidData);
+
+    let io_error = RespError::from(io::Error::other("reset"));
+    assert_eq!(io_error.clone(), RespError::String("reset".to_string()));
+}
+
+#[test]
+fn test_read_bulk_into_truncated_payload() {
+    let mut buf = [0; 100];
+
+    assert_eq!(
+        read_bulk_into(&mut &b"$100\r\nabc"[..], &mut buf),
+        Err(RespError::Truncated {
+            partial: b"$100\r\nabc".to_vec()
+        })
+    );
+}
+
+#[test]
+fn test_read_bulk_into_truncated_header_and_terminator() {
+    assert_eq!(
+        read_bulk_into(&mut &b"$10"[..], &mut [0; 10]),
+        Err(RespError::Truncated {
+            partial: b"$10".to_vec()
+        })
+    );
+
+    assert_eq!(
+        read_bulk_into(&mut &b"$2\r\nhi\r"[..], &mut [0; 2]),
+        Err(RespError::Truncated {
+            partial: b"$2\r\nhi\r".to_vec()
+        })
+    );
+
+    assert_eq!(
+        read_bulk_into(&mut &b""[..], &mut [0; 2]),
+        Err(io::Error::from(io::ErrorKind::UnexpectedEof).into())
+    );
+}
+
+#[test]
+fn test_read_bulk_into_truncated_partial_is_capped() {
+    let mut data = b"$1000\r\n".to_vec();
+    data.extend_from_slice(&[b'x'; 500]);
+    let mut buf = vec![0; 1000];
+
+    assert_eq!(
+        read_bulk_into(&mut &data[..], &mut buf),
+        Err(RespError::Truncated {
+            partial: data[..MAX_PARTIAL_LEN].to_vec()
+        })
+    );
+}
+
+// Fails its first read with `Interrupted`, then reads from `data`.
+#[cfg(test)]
+struct InterruptedOnce<'a> {
+    interrupted: bool,
+    data: &'a [u8],
+}
+
+#[cfg(test)]
+impl Read for InterruptedOnce<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.interrupted {
+            self.interrupted = true;
+            return Err(io::Error::from(io::ErrorKind::Interrupted));
+        }
+        self.data.read(buf)
+    }
+}
+
+#[test]
+fn test_read_bulk_into_retries_interrupted_read() {
+    let mut reader = InterruptedOnce {
+        interrupted: false,
+        data: b"$5\r\nhello\r\n",
+    };
+    let mut buf = [0; 5];
+
+    assert_eq!(read_bulk_into(&mut reader, &mut buf).unwrap(), Some(5));
+    assert!(reader.interrupted);
+    assert_eq!(&buf, b"hello");
+}
+
+#[quickcheck]
+fn read_bulk_into_returns_encoded_data(data: Vec<u8>) -> bool {
+    let mut encoded = format!("${}\r\n", data.len()).into_bytes();
+    encoded.extend_from_slice(&data);
+    encoded.extend_from_slice(b"\r\n");
+
+    let mut buf = vec![0; data.len()];
+
+    read_bulk_into(&mut &encoded[..], &mut buf).is_ok() && buf == data
+}
+
+#[quickcheck]
+fn read_bulk_into_reports_truncated_prefix(data: Vec<u8>, cut: usize) -> bool {
+    let mut encoded = format!("${}\r\n", data.len()).into_bytes();
+    encoded.extend_from_slice(&data);
+    encoded.extend_from_slice(b"\r\n");
+
+    let prefix = &encoded[..1 + cut % (encoded.len() - 1)];
+    let mut buf = vec![0; data.len()];
+
+    let expected = RespError::Truncated {
+        partial: prefix[..prefix.len().min(MAX_PARTIAL_LEN)].to_vec(),
+    };
+
+    read_bulk_into(&mut &prefix[..], &mut buf) == Err(expected)
+}
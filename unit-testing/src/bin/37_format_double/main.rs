@@ -0,0 +1,103 @@
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+use std::env;
+
+// Significant digits needed to round-trip any f64.
+const PRECISION: i32 = 17;
+
+fn strip_trailing_zeros(digits: &str) -> &str {
+    if digits.contains('.') {
+        digits.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        digits
+    }
+}
+
+// Formats a double the way Redis does with `%.17g`: fixed notation when the
+// exponent is in [-4, 17), scientific notation otherwise, with trailing zeros
+// removed in both cases.
+fn format_double(f: f64) -> String {
+    if f.is_nan() {
+        return "nan".to_string();
+    }
+    if f.is_infinite() {
+        return if f > 0.0 { "inf" } else { "-inf" }.to_string();
+    }
+    if f == 0.0 {
+        return if f.is_sign_negative() { "-0" } else { "0" }.to_string();
+    }
+
+    // Rounding to the requested precision may bump the exponent (e.g.
+    // 9.99...e9 -> 1e10), so take it from the rounded scientific form.
+    let scientific = format!("{:.*e}", (PRECISION - 1) as usize, f);
+    let (mantissa, exponent) = scientific.split_at(scientific.find('e').unwrap());
+    let exponent: i32 = exponent[1..].parse().unwrap();
+
+    if (-4..PRECISION).contains(&exponent) {
+        let fixed = format!("{:.*}", (PRECISION - 1 - exponent) as usize, f);
+        strip_trailing_zeros(&fixed).to_string()
+    } else {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!(
+            "{}e{}{:02}",
+            strip_trailing_zeros(mantissa),
+            sign,
+            exponent.abs()
+        )
+    }
+}
+
+fn main() {
+    for arg in env::args().skip(1) {
+        match arg.parse::<f64>() {
+            Ok(f) => println!("{} -> {}", arg, format_double(f)),
+            Err(e) => println!("{} -> {}", arg, e),
+        }
+    }
+}
+
+#[test]
+fn test_format_double_full_precision() {
+    assert_eq!(format_double(0.1), "0.10000000000000001");
+    assert_eq!(format_double(1.1), "1.1000000000000001");
+    assert_eq!(format_double(1.0 / 3.0), "0.33333333333333331");
+}
+
+#[test]
+fn test_format_double_trims_trailing_zeros() {
+    assert_eq!(format_double(1.0), "1");
+    assert_eq!(format_double(-2.5), "-2.5");
+    assert_eq!(format_double(123456789.125), "123456789.125");
+    assert_eq!(format_double(0.0001), "0.0001");
+    assert_eq!(format_double(1e16), "10000000000000000");
+}
+
+#[test]
+fn test_format_double_scientific() {
+    assert_eq!(format_double(1e17), "1e+17");
+    assert_eq!(format_double(1e20), "1e+20");
+    assert_eq!(format_double(1.5e-5), "1.5e-05");
+    assert_eq!(format_double(5e-324), "4.9406564584124654e-324");
+    assert_eq!(format_double(f64::MAX), "1.7976931348623157e+308");
+}
+
+#[test]
+fn test_format_double_special_values() {
+    assert_eq!(format_double(f64::INFINITY), "inf");
+    assert_eq!(format_double(f64::NEG_INFINITY), "-inf");
+    assert_eq!(format_double(f64::NAN), "nan");
+    assert_eq!(format_double(0.0), "0");
+    assert_eq!(format_double(-0.0), "-0");
+}
+
+#[quickcheck]
+fn format_double_round_trips(f: f64) -> bool {
+    let parsed: f64 = format_double(f).parse().unwrap();
+
+    if f.is_nan() {
+        parsed.is_nan()
+    } else {
+        parsed == f
+    }
+}
@@ -0,0 +1,83 @@
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+use std::io::{self, Read};
+use std::iter;
+
+// Splits on `\r\n` only; a lone `\r` or `\n` stays part of the line. A trailing
+// terminator does not produce an empty last line, but an unterminated last
+// line is still yielded.
+fn lines(data: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut rest = Some(data);
+
+    iter::from_fn(move || {
+        let data = rest?;
+
+        match data.windows(2).position(|w| w == b"\r\n") {
+            Some(pos) => {
+                rest = Some(&data[pos + 2..]).filter(|r| !r.is_empty());
+                Some(&data[..pos])
+            }
+            None => {
+                rest = None;
+                Some(data).filter(|d| !d.is_empty())
+            }
+        }
+    })
+}
+
+fn main() -> io::Result<()> {
+    let mut data = Vec::new();
+    io::stdin().read_to_end(&mut data)?;
+
+    for line in lines(&data) {
+        println!("{:?}", String::from_utf8_lossy(line));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lines_skips_trailing_empty_segment() {
+    let result: Vec<&[u8]> = lines(b"a\r\nb\r\nc\r\n").collect();
+
+    assert_eq!(result, vec![&b"a"[..], b"b", b"c"]);
+}
+
+#[test]
+fn test_lines_unterminated_last_line() {
+    let result: Vec<&[u8]> = lines(b"a\r\nb").collect();
+
+    assert_eq!(result, vec![&b"a"[..], b"b"]);
+}
+
+#[test]
+fn test_lines_empty_lines() {
+    let result: Vec<&[u8]> = lines(b"\r\n\r\nfoo\r\n").collect();
+
+    assert_eq!(result, vec![&b""[..], b"", b"foo"]);
+    assert_eq!(lines(b"").count(), 0);
+}
+
+#[test]
+fn test_lines_keeps_lone_cr_and_lf() {
+    let result: Vec<&[u8]> = lines(b"hel\rlo\r\nwor\nld\r\n").collect();
+
+    assert_eq!(result, vec![&b"hel\rlo"[..], b"wor\nld"]);
+}
+
+#[quickcheck]
+fn lines_splits_joined_lines(input: Vec<String>) -> bool {
+    let input: Vec<String> = input.into_iter().map(|s| s.replace('\r', "")).collect();
+
+    let mut data = Vec::new();
+    for line in &input {
+        data.extend_from_slice(line.as_bytes());
+        data.extend_from_slice(b"\r\n");
+    }
+
+    let result: Vec<&[u8]> = lines(&data).collect();
+    let expected: Vec<&[u8]> = input.iter().map(|s| s.as_bytes()).collect();
+
+    result == expected
+}
@@ -0,0 +1,102 @@
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+use std::env;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ReplyType {
+    SimpleString,
+    Error,
+    Integer,
+    BulkString,
+    Array,
+    Null,
+    Double,
+    Boolean,
+    BulkError,
+    VerbatimString,
+    BigNumber,
+    Map,
+    Set,
+    Attribute,
+    Push,
+}
+
+#[derive(Debug, PartialEq)]
+enum RespError {
+    NotEnoughData,
+    UnknownType(u8),
+}
+
+// Only looks at the first byte; the rest of the frame is not validated.
+fn resp_parse_type(data: &[u8]) -> Result<ReplyType, RespError> {
+    let reply_type = match data.first().ok_or(RespError::NotEnoughData)? {
+        b'+' => ReplyType::SimpleString,
+        b'-' => ReplyType::Error,
+        b':' => ReplyType::Integer,
+        b'$' => ReplyType::BulkString,
+        b'*' => ReplyType::Array,
+        b'_' => ReplyType::Null,
+        b',' => ReplyType::Double,
+        b'#' => ReplyType::Boolean,
+        b'!' => ReplyType::BulkError,
+        b'=' => ReplyType::VerbatimString,
+        b'(' => ReplyType::BigNumber,
+        b'%' => ReplyType::Map,
+        b'~' => ReplyType::Set,
+        b'|' => ReplyType::Attribute,
+        b'>' => ReplyType::Push,
+        &b => return Err(RespError::UnknownType(b)),
+    };
+
+    Ok(reply_type)
+}
+
+fn main() {
+    for arg in env::args().skip(1) {
+        println!("{} -> {:?}", arg, resp_parse_type(arg.as_bytes()));
+    }
+}
+
+#[test]
+fn test_resp_parse_type_resp2() {
+    assert_eq!(resp_parse_type(b"+OK\r\n"), Ok(ReplyType::SimpleString));
+    assert_eq!(resp_parse_type(b"-ERR x\r\n"), Ok(ReplyType::Error));
+    assert_eq!(resp_parse_type(b":1\r\n"), Ok(ReplyType::Integer));
+    assert_eq!(resp_parse_type(b"$3\r\nfoo\r\n"), Ok(ReplyType::BulkString));
+    assert_eq!(resp_parse_type(b"*0\r\n"), Ok(ReplyType::Array));
+}
+
+#[test]
+fn test_resp_parse_type_resp3() {
+    assert_eq!(resp_parse_type(b"_\r\n"), Ok(ReplyType::Null));
+    assert_eq!(resp_parse_type(b",1.5\r\n"), Ok(ReplyType::Double));
+    assert_eq!(resp_parse_type(b"#t\r\n"), Ok(ReplyType::Boolean));
+    assert_eq!(resp_parse_type(b"!3\r\nERR\r\n"), Ok(ReplyType::BulkError));
+    assert_eq!(
+        resp_parse_type(b"=8\r\ntxt:hello\r\n"),
+        Ok(ReplyType::VerbatimString)
+    );
+    assert_eq!(resp_parse_type(b"(12345\r\n"), Ok(ReplyType::BigNumber));
+    assert_eq!(resp_parse_type(b"%0\r\n"), Ok(ReplyType::Map));
+    assert_eq!(resp_parse_type(b"~0\r\n"), Ok(ReplyType::Set));
+    assert_eq!(resp_parse_type(b"|0\r\n"), Ok(ReplyType::Attribute));
+    assert_eq!(resp_parse_type(b">0\r\n"), Ok(ReplyType::Push));
+}
+
+#[test]
+fn test_resp_parse_type_invalid() {
+    assert_eq!(
+        resp_parse_type(b"@foo\r\n"),
+        Err(RespError::UnknownType(b'@'))
+    );
+    assert_eq!(resp_parse_type(b""), Err(RespError::NotEnoughData));
+}
+
+#[quickcheck]
+fn resp_parse_type_only_depends_on_first_byte(first: u8, rest: Vec<u8>) -> bool {
+    let mut data = vec![first];
+    data.extend_from_slice(&rest);
+
+    resp_parse_type(&data) == resp_parse_type(&[first])
+}
@@ -0,0 +1,164 @@
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::env;
+use std::io::{self, Write};
+use std::time::Instant;
+
+// Prefix byte, up to 20 decimal digits for a 64-bit usize, and `\r\n`.
+const MAX_PREFIX_LEN: usize = 1 + 20 + 2;
+
+const BENCH_ITERATIONS: usize = 1_000_000;
+
+// Counts allocations per thread, so that measurements are not disturbed by
+// tests running in parallel.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count_allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+// Writes e.g. `$11\r\n` from a stack buffer, without the temporary String
+// that `format!` would allocate.
+fn write_len_prefix<W: Write>(writer: &mut W, prefix: u8, n: usize) -> io::Result<()> {
+    let mut buf = [0; MAX_PREFIX_LEN];
+    let mut pos = buf.len();
+
+    pos -= 2;
+    buf[pos..].copy_from_slice(b"\r\n");
+
+    let mut n = n;
+    loop {
+        pos -= 1;
+        buf[pos] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+
+    pos -= 1;
+    buf[pos] = prefix;
+
+    writer.write_all(&buf[pos..])
+}
+
+fn write_len_prefix_with_format<W: Write>(writer: &mut W, prefix: u8, n: usize) -> io::Result<()> {
+    writer.write_all(format!("{}{}\r\n", prefix as char, n).as_bytes())
+}
+
+fn bench<F: FnMut(&mut Vec<u8>, usize) -> io::Result<()>>(name: &str, mut f: F) -> io::Result<()> {
+    let mut out = Vec::with_capacity(MAX_PREFIX_LEN);
+    let mut result = Ok(());
+
+    let start = Instant::now();
+    let allocations = count_allocations(|| {
+        for n in 0..BENCH_ITERATIONS {
+            out.clear();
+            if let Err(e) = f(&mut out, n) {
+                result = Err(e);
+                return;
+            }
+        }
+    });
+    let elapsed = start.elapsed();
+
+    println!(
+        "{}: {} iterations in {:?}, {} allocations",
+        name, BENCH_ITERATIONS, elapsed, allocations
+    );
+
+    result
+}
+
+// Run with `cargo run --release --bin 40_write_len_prefix -- bench` to compare
+// against the `format!`-based version.
+fn main() -> io::Result<()> {
+    if env::args().nth(1).as_deref() == Some("bench") {
+        bench("write_len_prefix", |out, n| write_len_prefix(out, b'$', n))?;
+        bench("format!", |out, n| {
+            write_len_prefix_with_format(out, b'$', n)
+        })?;
+        return Ok(());
+    }
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    let value = b"hello world";
+    write_len_prefix(&mut out, b'$', value.len())?;
+    out.write_all(value)?;
+    out.write_all(b"\r\n")
+}
+
+#[test]
+fn test_write_len_prefix_bulk_string() {
+    let mut out = Vec::new();
+    write_len_prefix(&mut out, b'$', 11).unwrap();
+
+    assert_eq!(out, b"$11\r\n");
+}
+
+#[test]
+fn test_write_len_prefix_edge_values() {
+    let mut out = Vec::new();
+    write_len_prefix(&mut out, b'*', 0).unwrap();
+    write_len_prefix(&mut out, b'$', usize::MAX).unwrap();
+
+    assert_eq!(out, format!("*0\r\n${}\r\n", usize::MAX).into_bytes());
+}
+
+#[test]
+fn test_write_len_prefix_does_not_allocate() {
+    let mut out = Vec::with_capacity(MAX_PREFIX_LEN * 100);
+
+    let with_prefix = count_allocations(|| {
+        for n in 0..100 {
+            write_len_prefix(&mut out, b'$', n).unwrap();
+        }
+    });
+
+    out.clear();
+
+    let with_format = count_allocations(|| {
+        for n in 0..100 {
+            write_len_prefix_with_format(&mut out, b'$', n).unwrap();
+        }
+    });
+
+    assert_eq!(with_prefix, 0);
+    assert!(with_format >= 100);
+}
+
+#[quickcheck]
+fn write_len_prefix_matches_format(prefix: u8, n: usize) -> bool {
+    let mut out = Vec::new();
+    write_len_prefix(&mut out, prefix, n).unwrap();
+
+    let mut expected = vec![prefix];
+    expected.extend_from_slice(format!("{}\r\n", n).as_bytes());
+
+    out == expected
+}